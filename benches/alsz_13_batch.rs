@@ -0,0 +1,51 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_mpc_test::oblivious_transfer::alsz_13::{
+    first_round, output_computation, second_round, setup, DefaultCurve,
+};
+
+// Sequential baseline mirroring what `first_round_batch`/`second_round_batch`/
+// `output_computation_batch` used to do before they were switched to rayon.
+fn run_batch_sequential(n: usize) {
+    let h = setup::<DefaultCurve>();
+    let sigma_vec: Vec<u8> = (0..n).map(|i| (i % 2) as u8).collect();
+    let x_0_vec: Vec<Vec<u8>> = (0..n).map(|_| vec![0u8; 32]).collect();
+    let x_1_vec: Vec<Vec<u8>> = (0..n).map(|_| vec![1u8; 32]).collect();
+
+    for i in 0..n {
+        let (a_i, h_0, h_1, proof) = first_round(sigma_vec[i], h);
+        let (u, v_0, v_1) = second_round(&x_0_vec[i], h_0, &x_1_vec[i], h_1, h, &proof, i as u64);
+        output_computation(sigma_vec[i], a_i, u, v_0, v_1, i as u64);
+    }
+}
+
+fn run_batch_parallel(n: usize) {
+    use rust_mpc_test::oblivious_transfer::alsz_13::{
+        first_round_batch, output_computation_batch, second_round_batch,
+    };
+
+    let h = setup::<DefaultCurve>();
+    let sigma_vec: Vec<u8> = (0..n).map(|i| (i % 2) as u8).collect();
+    let x_0_vec: Vec<Vec<u8>> = (0..n).map(|_| vec![0u8; 32]).collect();
+    let x_1_vec: Vec<Vec<u8>> = (0..n).map(|_| vec![1u8; 32]).collect();
+
+    let (a_i_vec, h_0_vec, h_1_vec, proof_vec) = first_round_batch(sigma_vec.clone(), h);
+    let (u_vec, v_0_vec, v_1_vec) =
+        second_round_batch(x_0_vec, h_0_vec, x_1_vec, h_1_vec, h, &proof_vec);
+    output_computation_batch(sigma_vec, a_i_vec, u_vec, v_0_vec, v_1_vec);
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ot_batch");
+    for n in [64usize, 1024, 8192] {
+        group.bench_with_input(BenchmarkId::new("sequential", n), &n, |b, &n| {
+            b.iter(|| run_batch_sequential(n));
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", n), &n, |b, &n| {
+            b.iter(|| run_batch_parallel(n));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch);
+criterion_main!(benches);
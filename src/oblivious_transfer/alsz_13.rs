@@ -1,7 +1,11 @@
-use curv::elliptic::curves::ed25519::{FE, GE};
+use curv::elliptic::curves::ed25519::{FE as Ed25519Scalar, GE as Ed25519Point};
+use curv::elliptic::curves::secp256k1::{FE as Secp256k1Scalar, GE as Secp256k1Point};
 use curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use curv::BigInt;
 use itertools::multizip;
+use rayon::prelude::*;
 use sha2::{Digest, Sha256};
+use std::ops::{Add, Mul, Sub};
 use rand;
 
 // Implementation of semi-honest OT protocol Protocol 5.1
@@ -14,87 +18,343 @@ use rand;
 // note = {\url{https://eprint.iacr.org/2013/552}},
 // }
 
-pub fn first_round(sigma: u8) -> (FE, GE, GE) {
+// Bridges whichever `curv` elliptic-curve backend is plugged in as `C` so the protocol below
+// can be written once and instantiated over ed25519 or secp256k1 (the two impls below); another
+// `curv` curve needs its own impl of this trait.
+pub trait OtCurve:
+    Copy
+    + PartialEq
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<<Self as OtCurve>::Scalar, Output = Self>
+{
+    type Scalar: Copy
+        + PartialEq
+        + Send
+        + Sync
+        + Add<Output = Self::Scalar>
+        + Sub<Output = Self::Scalar>
+        + Mul<Output = Self::Scalar>;
+
+    fn generator() -> Self;
+    fn nums_point() -> Self;
+    fn random_scalar() -> Self::Scalar;
+    fn to_bytes(&self) -> Vec<u8>;
+    fn scalar_from_bytes(bytes: &[u8]) -> Self::Scalar;
+}
+
+impl OtCurve for Ed25519Point {
+    type Scalar = Ed25519Scalar;
+
+    fn generator() -> Self {
+        Ed25519Point::generator()
+    }
+    fn nums_point() -> Self {
+        Ed25519Point::base_point2()
+    }
+    fn random_scalar() -> Self::Scalar {
+        Ed25519Scalar::new_random()
+    }
+    fn to_bytes(&self) -> Vec<u8> {
+        self.get_element().to_bytes().to_vec()
+    }
+    fn scalar_from_bytes(bytes: &[u8]) -> Self::Scalar {
+        ECScalar::from(&BigInt::from_bytes(bytes))
+    }
+}
+
+impl OtCurve for Secp256k1Point {
+    type Scalar = Secp256k1Scalar;
+
+    fn generator() -> Self {
+        Secp256k1Point::generator()
+    }
+    fn nums_point() -> Self {
+        Secp256k1Point::base_point2()
+    }
+    fn random_scalar() -> Self::Scalar {
+        Secp256k1Scalar::new_random()
+    }
+    fn to_bytes(&self) -> Vec<u8> {
+        self.get_element().to_bytes().to_vec()
+    }
+    fn scalar_from_bytes(bytes: &[u8]) -> Self::Scalar {
+        ECScalar::from(&BigInt::from_bytes(bytes))
+    }
+}
+
+// The original protocol ran over ed25519 only; this alias names that backend so callers can
+// write e.g. `setup::<DefaultCurve>()` without naming the curv type.
+pub type DefaultCurve = Ed25519Point;
+
+// CRS for the receiver's key generation: a group element whose discrete log nobody knows,
+// so that `h_0 * h_1 == h` can be enforced instead of letting the receiver pick both freely.
+pub fn setup<C: OtCurve>() -> C {
+    C::nums_point()
+}
+
+// Fiat-Shamir OR-proof that the receiver knows `a` with `g^a == h_0` or `g^a == h * h_0^{-1}`,
+// i.e. that exactly one of `h_0`, `h_1` was honestly derived from a known exponent.
+pub struct ReceiverProof<C: OtCurve> {
+    t_0: C,
+    t_1: C,
+    c_0: C::Scalar,
+    c_1: C::Scalar,
+    z_0: C::Scalar,
+    z_1: C::Scalar,
+}
+
+fn challenge<C: OtCurve>(h: C, h_0: C, h_1: C, t_0: C, t_1: C) -> C::Scalar {
+    let mut sh = Sha256::new();
+    for p in [h, h_0, h_1, t_0, t_1].iter() {
+        sh.update(p.to_bytes());
+    }
+    C::scalar_from_bytes(sh.finalize().as_slice())
+}
+
+fn prove_receiver<C: OtCurve>(sigma: u8, a_i: C::Scalar, h: C, h_0: C, h_1: C) -> ReceiverProof<C> {
+    let g = C::generator();
+    let w_real = C::random_scalar();
+    let c_fake = C::random_scalar();
+    let z_fake = C::random_scalar();
+    let t_real = g * w_real;
+    let h_fake = if sigma == 0 { h_1 } else { h_0 };
+    let t_fake = g * z_fake - h_fake * c_fake;
+    let (t_0, t_1) = if sigma == 0 { (t_real, t_fake) } else { (t_fake, t_real) };
+    let c = challenge(h, h_0, h_1, t_0, t_1);
+    let c_real = c - c_fake;
+    let z_real = w_real + c_real * a_i;
+    let (c_0, z_0, c_1, z_1) = if sigma == 0 {
+        (c_real, z_real, c_fake, z_fake)
+    } else {
+        (c_fake, z_fake, c_real, z_real)
+    };
+    ReceiverProof { t_0, t_1, c_0, c_1, z_0, z_1 }
+}
+
+fn verify_receiver<C: OtCurve>(h: C, h_0: C, h_1: C, proof: &ReceiverProof<C>) -> bool {
+    let g = C::generator();
+    let c = challenge(h, h_0, h_1, proof.t_0, proof.t_1);
+    if proof.c_0 + proof.c_1 != c {
+        return false;
+    }
+    if g * proof.z_0 != proof.t_0 + h_0 * proof.c_0 {
+        return false;
+    }
+    if g * proof.z_1 != proof.t_1 + h_1 * proof.c_1 {
+        return false;
+    }
+    true
+}
+
+pub fn first_round<C: OtCurve>(sigma: u8, h: C) -> (C::Scalar, C, C, ReceiverProof<C>) {
     assert!(sigma == 0 || sigma == 1);
-    let g = GE::generator();
-    let a_i = FE::new_random();
-    let h_i = g * FE::new_random(); // TODO: sample random element
+    let g = C::generator();
+    let a_i = C::random_scalar();
     let (h_0, h_1) = if sigma == 0 {
         let h_0 = g * a_i;
-        let h_1 = h_i;
+        let h_1 = h - h_0;
         (h_0, h_1)
     } else {
-        let h_0 = h_i;
         let h_1 = g * a_i;
+        let h_0 = h - h_1;
         (h_0, h_1)
     };
-    (a_i, h_0, h_1)
+    let proof = prove_receiver(sigma, a_i, h, h_0, h_1);
+    (a_i, h_0, h_1, proof)
 }
 
-pub fn first_round_batch(sigma_vec: Vec<u8>) -> (Vec<FE>, Vec<GE>, Vec<GE>) {
-    let tmp_vec: Vec<(FE, GE, GE)> = sigma_vec.iter().map(|&x| first_round(x)).collect();
-    let a_i_vec = tmp_vec.iter().map(|x|{x.0}).collect();
-    let h_0_vec = tmp_vec.iter().map(|x|{x.1}).collect();
-    let h_1_vec = tmp_vec.iter().map(|x|{x.2}).collect();
-    (a_i_vec, h_0_vec, h_1_vec)
+pub fn first_round_batch<C: OtCurve>(
+    sigma_vec: Vec<u8>,
+    h: C,
+) -> (Vec<C::Scalar>, Vec<C>, Vec<C>, Vec<ReceiverProof<C>>) {
+    let tmp_vec: Vec<(C::Scalar, C, C, ReceiverProof<C>)> =
+        sigma_vec.into_par_iter().map(|x| first_round(x, h)).collect();
+    let a_i_vec = tmp_vec.iter().map(|x| x.0).collect();
+    let h_0_vec = tmp_vec.iter().map(|x| x.1).collect();
+    let h_1_vec = tmp_vec.iter().map(|x| x.2).collect();
+    let proof_vec = tmp_vec.into_iter().map(|x| x.3).collect();
+    (a_i_vec, h_0_vec, h_1_vec, proof_vec)
 }
 
-fn h(x: Vec<u8>) -> Vec<u8> {
-    let mut sh = Sha256::new();
-    sh.update(x);
-    sh.finalize().as_slice().to_vec()
+// Derives a keystream of exactly `len` bytes from `k` via SHA-256 in counter mode, folding in
+// `index` (the OT instance's position in the batch) and a per-block counter into the nonce so
+// that no keystream is ever reused, whatever the payload length or batch position. A SHA-256
+// counter-mode construction (NIST SP 800-108-style expansion) gets the same "expand to any
+// length, never repeat a block" property as AES-CTR/ChaCha20 without pulling in a stream-cipher
+// crate, since `sha2` is already a dependency here; swap in one if AES-NI throughput matters.
+pub(crate) fn kdf(k: &Vec<u8>, index: u64, len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len);
+    let mut counter: u64 = 0;
+    while out.len() < len {
+        let mut sh = Sha256::new();
+        sh.update(k);
+        sh.update(index.to_le_bytes());
+        sh.update(counter.to_le_bytes());
+        let block = sh.finalize();
+        let take = (len - out.len()).min(block.len());
+        out.extend_from_slice(&block[..take]);
+        counter += 1;
+    }
+    out
 }
 
 fn xor(x: &Vec<u8>, y: &Vec<u8>) -> Vec<u8> {
     x.iter().zip(y.iter()).map(|(&a, &b)| a ^ b).collect()
 }
 
-pub fn second_round(x_0: &Vec<u8>, h_0: GE, x_1: &Vec<u8>, h_1: GE) -> (GE, Vec<u8>, Vec<u8>) {
-    let g = GE::generator();
-    let r = FE::new_random();
+pub fn second_round<C: OtCurve>(
+    x_0: &Vec<u8>,
+    h_0: C,
+    x_1: &Vec<u8>,
+    h_1: C,
+    h: C,
+    proof: &ReceiverProof<C>,
+    index: u64,
+) -> (C, Vec<u8>, Vec<u8>) {
+    assert!(h_0 + h_1 == h);
+    assert!(verify_receiver(h, h_0, h_1, proof));
+    let g = C::generator();
+    let r = C::random_scalar();
     let u = g * r;
     let k_0 = h_0 * r;
     let k_1 = h_1 * r;
-    let kdf_0 = h(k_0.get_element().to_bytes().to_vec());
+    let kdf_0 = kdf(&k_0.to_bytes(), index, x_0.len());
     let v_0 = xor(&kdf_0, &x_0);
-    let kdf_1 = h(k_1.get_element().to_bytes().to_vec());
+    let kdf_1 = kdf(&k_1.to_bytes(), index, x_1.len());
     let v_1 = xor(&kdf_1, &x_1);
     (u, v_0, v_1)
 }
 
-pub fn second_round_batch(
+pub fn second_round_batch<C: OtCurve>(
     x_0_vec: Vec<Vec<u8>>,
-    h_0_vec: Vec<GE>,
+    h_0_vec: Vec<C>,
     x_1_vec: Vec<Vec<u8>>,
-    h_1_vec: Vec<GE>,
-) -> (Vec<GE>, Vec<Vec<u8>>, Vec<Vec<u8>>) {
-    let tmp_vec: Vec<(GE, Vec<u8>, Vec<u8>)> = multizip((x_0_vec, h_0_vec, x_1_vec, h_1_vec))
-        .map(|(x_0, h_0, x_1, h_1)| second_round(&x_0, h_0, &x_1, h_1))
+    h_1_vec: Vec<C>,
+    h: C,
+    proof_vec: &Vec<ReceiverProof<C>>,
+) -> (Vec<C>, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let tmp_vec: Vec<(C, Vec<u8>, Vec<u8>)> = x_0_vec
+        .into_par_iter()
+        .zip(h_0_vec.into_par_iter())
+        .zip(x_1_vec.into_par_iter())
+        .zip(h_1_vec.into_par_iter())
+        .zip(proof_vec.par_iter())
+        .enumerate()
+        .map(|(index, ((((x_0, h_0), x_1), h_1), proof))| {
+            second_round(&x_0, h_0, &x_1, h_1, h, proof, index as u64)
+        })
         .collect();
-    let u_vec = tmp_vec.iter().map(|x|{x.0}).collect();
-    let v_0_vec = tmp_vec.iter().map(|x|{x.1.clone()}).collect();
-    let v_1_vec = tmp_vec.iter().map(|x|{x.2.clone()}).collect();
+    let u_vec = tmp_vec.iter().map(|x| x.0).collect();
+    let v_0_vec = tmp_vec.iter().map(|x| x.1.clone()).collect();
+    let v_1_vec = tmp_vec.iter().map(|x| x.2.clone()).collect();
     (u_vec, v_0_vec, v_1_vec)
 }
 
-pub fn output_computation(sigma: u8, a_i: FE, u: GE, v_0: Vec<u8>, v_1: Vec<u8>) -> Vec<u8> {
+pub fn output_computation<C: OtCurve>(
+    sigma: u8,
+    a_i: C::Scalar,
+    u: C,
+    v_0: Vec<u8>,
+    v_1: Vec<u8>,
+    index: u64,
+) -> Vec<u8> {
     assert!(sigma == 0 || sigma == 1);
     let v_sigma = if sigma == 0 { v_0 } else { v_1 };
     let k_sigma = u * a_i;
-    let kdf_sigma = h(k_sigma.get_element().to_bytes().to_vec());
+    let kdf_sigma = kdf(&k_sigma.to_bytes(), index, v_sigma.len());
     let x_sigma = xor(&v_sigma, &kdf_sigma);
     x_sigma
 }
 
-pub fn output_computation_batch(
+pub fn output_computation_batch<C: OtCurve>(
     sigma_vec: Vec<u8>,
-    a_i_vec: Vec<FE>,
-    u_vec: Vec<GE>,
+    a_i_vec: Vec<C::Scalar>,
+    u_vec: Vec<C>,
     v_0_vec: Vec<Vec<u8>>,
     v_1_vec: Vec<Vec<u8>>,
 ) -> Vec<Vec<u8>> {
-    multizip((sigma_vec, a_i_vec, u_vec, v_0_vec, v_1_vec))
-        .map(|(sigma, a_i, u, v_0, v_1)| output_computation(sigma, a_i, u, v_0, v_1))
+    sigma_vec
+        .into_par_iter()
+        .zip(a_i_vec.into_par_iter())
+        .zip(u_vec.into_par_iter())
+        .zip(v_0_vec.into_par_iter())
+        .zip(v_1_vec.into_par_iter())
+        .enumerate()
+        .map(|(index, ((((sigma, a_i), u), v_0), v_1))| {
+            output_computation(sigma, a_i, u, v_0, v_1, index as u64)
+        })
+        .collect()
+}
+
+// Random-OT precomputation (Beaver's OT precomputation): instead of masking caller-chosen
+// messages, the sender derives two uniformly random pads `(m_0, m_1)` and the receiver comes
+// away with only `m_sigma`, for whichever bit it committed to ahead of time. The `random_ot`
+// module turns these pads into chosen-message OTs online with a single correction bit.
+pub fn second_round_random<C: OtCurve>(
+    h_0: C,
+    h_1: C,
+    h: C,
+    proof: &ReceiverProof<C>,
+    index: u64,
+    pad_len: usize,
+) -> (C, Vec<u8>, Vec<u8>) {
+    assert!(h_0 + h_1 == h);
+    assert!(verify_receiver(h, h_0, h_1, proof));
+    let g = C::generator();
+    let r = C::random_scalar();
+    let u = g * r;
+    let k_0 = h_0 * r;
+    let k_1 = h_1 * r;
+    let m_0 = kdf(&k_0.to_bytes(), index, pad_len);
+    let m_1 = kdf(&k_1.to_bytes(), index, pad_len);
+    (u, m_0, m_1)
+}
+
+pub fn second_round_random_batch<C: OtCurve>(
+    h_0_vec: Vec<C>,
+    h_1_vec: Vec<C>,
+    h: C,
+    proof_vec: &Vec<ReceiverProof<C>>,
+    pad_len: usize,
+) -> (Vec<C>, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let tmp_vec: Vec<(C, Vec<u8>, Vec<u8>)> = h_0_vec
+        .into_par_iter()
+        .zip(h_1_vec.into_par_iter())
+        .zip(proof_vec.par_iter())
+        .enumerate()
+        .map(|(index, ((h_0, h_1), proof))| {
+            second_round_random(h_0, h_1, h, proof, index as u64, pad_len)
+        })
+        .collect();
+    let u_vec = tmp_vec.iter().map(|x| x.0).collect();
+    let m_0_vec = tmp_vec.iter().map(|x| x.1.clone()).collect();
+    let m_1_vec = tmp_vec.iter().map(|x| x.2.clone()).collect();
+    (u_vec, m_0_vec, m_1_vec)
+}
+
+pub fn output_computation_random<C: OtCurve>(
+    a_i: C::Scalar,
+    u: C,
+    index: u64,
+    pad_len: usize,
+) -> Vec<u8> {
+    let k_sigma = u * a_i;
+    kdf(&k_sigma.to_bytes(), index, pad_len)
+}
+
+pub fn output_computation_random_batch<C: OtCurve>(
+    a_i_vec: Vec<C::Scalar>,
+    u_vec: Vec<C>,
+    pad_len: usize,
+) -> Vec<Vec<u8>> {
+    a_i_vec
+        .into_par_iter()
+        .zip(u_vec.into_par_iter())
+        .enumerate()
+        .map(|(index, (a_i, u))| output_computation_random(a_i, u, index as u64, pad_len))
         .collect()
 }
 
@@ -102,33 +362,108 @@ pub fn output_computation_batch(
 mod tests {
     use super::*;
 
-    #[test]
-    fn semi_honest_ot() {
+    fn random_bytes(n: usize) -> Vec<u8> {
+        (0..n).map(|_| rand::random::<u8>()).collect()
+    }
+
+    fn semi_honest_ot<C: OtCurve>() {
+        let h = setup::<C>();
+
         let sigma = 0;
-        let x_0 = FE::new_random().get_element().to_bytes().to_vec();
-        let x_1 = FE::new_random().get_element().to_bytes().to_vec();
+        let x_0 = random_bytes(32);
+        let x_1 = random_bytes(32);
 
-        let (a_i, h_0, h_1) = first_round(sigma);
-        let (u, v_0, v_1) = second_round(&x_0, h_0, &x_1, h_1);
-        let x = output_computation(sigma, a_i, u, v_0, v_1);
+        let (a_i, h_0, h_1, proof) = first_round(sigma, h);
+        let (u, v_0, v_1) = second_round(&x_0, h_0, &x_1, h_1, h, &proof, 0);
+        let x = output_computation(sigma, a_i, u, v_0, v_1, 0);
 
         assert_eq!(x_0, x);
 
         let sigma = 1;
-        let x_0 = FE::new_random().get_element().to_bytes().to_vec();
-        let x_1 = FE::new_random().get_element().to_bytes().to_vec();
+        let x_0 = random_bytes(32);
+        let x_1 = random_bytes(32);
+
+        let (a_i, h_0, h_1, proof) = first_round(sigma, h);
+        let (u, v_0, v_1) = second_round(&x_0, h_0, &x_1, h_1, h, &proof, 0);
+        let x = output_computation(sigma, a_i, u, v_0, v_1, 0);
+
+        assert_eq!(x_1, x);
+    }
+
+    #[test]
+    fn semi_honest_ot_ed25519() {
+        semi_honest_ot::<Ed25519Point>();
+    }
+
+    #[test]
+    fn semi_honest_ot_secp256k1() {
+        semi_honest_ot::<Secp256k1Point>();
+    }
+
+    #[test]
+    fn semi_honest_ot_large_payload() {
+        let h = setup::<Ed25519Point>();
+        let sigma = 1;
+        let x_0 = vec![0xabu8; 1024];
+        let x_1 = vec![0xcdu8; 1024];
 
-        let (a_i, h_0, h_1) = first_round(sigma);
-        let (u, v_0, v_1) = second_round(&x_0, h_0, &x_1, h_1);
-        let x = output_computation(sigma, a_i, u, v_0, v_1);
+        let (a_i, h_0, h_1, proof) = first_round(sigma, h);
+        let (u, v_0, v_1) = second_round(&x_0, h_0, &x_1, h_1, h, &proof, 0);
+        let x = output_computation(sigma, a_i, u, v_0, v_1, 0);
 
         assert_eq!(x_1, x);
+        assert_eq!(x.len(), 1024);
+    }
+
+    #[test]
+    #[should_panic]
+    fn second_round_rejects_mismatched_h() {
+        let h = setup::<Ed25519Point>();
+        let g = Ed25519Point::generator();
+        // A cheating receiver picks h_0, h_1 independently instead of deriving them from `h`,
+        // so `h_0 + h_1 != h` and second_round aborts on that check alone.
+        let h_0 = g * Ed25519Point::random_scalar();
+        let h_1 = g * Ed25519Point::random_scalar();
+        let fake_proof = prove_receiver(0, Ed25519Point::random_scalar(), h, h_0, h_1);
+
+        let x_0 = vec![0u8; 32];
+        let x_1 = vec![1u8; 32];
+        second_round(&x_0, h_0, &x_1, h_1, h, &fake_proof, 0);
+    }
+
+    #[test]
+    fn malicious_receiver_cannot_learn_both_messages() {
+        let h = setup::<Ed25519Point>();
+        let g = Ed25519Point::generator();
+        // `h_0 + h_1 == h` holds, so the algebraic check passes; the receiver doesn't actually
+        // know the discrete log of `h_0` (it used an unrelated scalar), so the NIZK should be
+        // the thing that catches it.
+        let h_0 = g * Ed25519Point::random_scalar();
+        let h_1 = h - h_0;
+        let unrelated_a = Ed25519Point::random_scalar();
+        let forged_proof = prove_receiver(0, unrelated_a, h, h_0, h_1);
+
+        assert!(!verify_receiver(h, h_0, h_1, &forged_proof));
+    }
+
+    #[test]
+    #[should_panic]
+    fn second_round_rejects_forged_proof() {
+        let h = setup::<Ed25519Point>();
+        let g = Ed25519Point::generator();
+        let h_0 = g * Ed25519Point::random_scalar();
+        let h_1 = h - h_0;
+        let forged_proof = prove_receiver(0, Ed25519Point::random_scalar(), h, h_0, h_1);
+
+        let x_0 = vec![0u8; 32];
+        let x_1 = vec![1u8; 32];
+        second_round(&x_0, h_0, &x_1, h_1, h, &forged_proof, 0);
     }
 
     fn generate_test_inputs() -> (u8, Vec<u8>, Vec<u8>) {
         let sigma = rand::random::<bool>() as u8;
-        let x_0 = FE::new_random().get_element().to_bytes().to_vec();
-        let x_1 = FE::new_random().get_element().to_bytes().to_vec();
+        let x_0 = random_bytes(32);
+        let x_1 = random_bytes(32);
         (sigma, x_0, x_1)
     }
 
@@ -140,15 +475,55 @@ mod tests {
         (sigma_vec, x_0_vec, x_1_vec)
     }
 
-    #[test]
-    fn semi_honest_ot_batch_3() {
+    fn semi_honest_ot_batch_3<C: OtCurve>() {
+        let h = setup::<C>();
         let (sigma_vec, x_0_vec, x_1_vec) = generate_test_inputs_batch(3);
-        let (a_i_vec, h_0_vec, h_1_vec) = first_round_batch(sigma_vec.clone());
-        let (u_vec, v_0_vec, v_1_vec) = second_round_batch(x_0_vec.clone(), h_0_vec, x_1_vec.clone(), h_1_vec);
+        let (a_i_vec, h_0_vec, h_1_vec, proof_vec) = first_round_batch(sigma_vec.clone(), h);
+        let (u_vec, v_0_vec, v_1_vec) =
+            second_round_batch(x_0_vec.clone(), h_0_vec, x_1_vec.clone(), h_1_vec, h, &proof_vec);
         let x_vec = output_computation_batch(sigma_vec.clone(), a_i_vec, u_vec, v_0_vec, v_1_vec);
         multizip((sigma_vec, x_vec, x_0_vec, x_1_vec)).map(|(sigma, x, x_0, x_1)|{
             if sigma == 0 { assert_eq!(x, x_0) }
             if sigma == 1 { assert_eq!(x, x_1) }
         }).for_each(drop);
     }
+
+    #[test]
+    fn semi_honest_ot_batch_3_ed25519() {
+        semi_honest_ot_batch_3::<Ed25519Point>();
+    }
+
+    #[test]
+    fn semi_honest_ot_batch_3_secp256k1() {
+        semi_honest_ot_batch_3::<Secp256k1Point>();
+    }
+
+    #[test]
+    fn batch_results_identical_regardless_of_thread_count() {
+        let h = setup::<Ed25519Point>();
+        let (sigma_vec, x_0_vec, x_1_vec) = generate_test_inputs_batch(8);
+        let (a_i_vec, h_0_vec, h_1_vec, proof_vec) = first_round_batch(sigma_vec.clone(), h);
+        let (u_vec, v_0_vec, v_1_vec) =
+            second_round_batch(x_0_vec, h_0_vec, x_1_vec, h_1_vec, h, &proof_vec);
+
+        // output_computation_batch has no internal randomness, so its result must be identical
+        // no matter how many rayon threads end up servicing the batch.
+        let single_threaded = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let multi_threaded = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+        let x_vec_1 = single_threaded.install(|| {
+            output_computation_batch(
+                sigma_vec.clone(),
+                a_i_vec.clone(),
+                u_vec.clone(),
+                v_0_vec.clone(),
+                v_1_vec.clone(),
+            )
+        });
+        let x_vec_2 = multi_threaded.install(|| {
+            output_computation_batch(sigma_vec, a_i_vec, u_vec, v_0_vec, v_1_vec)
+        });
+
+        assert_eq!(x_vec_1, x_vec_2);
+    }
 }
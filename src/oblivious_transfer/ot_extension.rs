@@ -0,0 +1,179 @@
+use super::alsz_13::{
+    first_round_batch, kdf, output_computation_batch, second_round_batch, setup, OtCurve,
+    ReceiverProof,
+};
+use itertools::multizip;
+
+// IKNP OT extension: turn KAPPA base OTs into m string-OTs, generic over the same `OtCurve`
+// the base OT runs on (chunk0-4).
+// https://www.iacr.org/archive/crypto2003/27290145/27290145.pdf
+// @inproceedings{ishai2003extending,
+// title={Extending oblivious transfers efficiently},
+// author={Ishai, Yuval and Kilian, Joe and Nissim, Kobbi and Petrank, Erez},
+// booktitle={CRYPTO},
+// year={2003},
+// }
+
+pub const KAPPA: usize = 128;
+
+pub struct ReceiverExtensionState {
+    r: Vec<u8>,
+    t_cols: Vec<Vec<u8>>,
+}
+
+// G: expands a seed into `len` pseudorandom bits (0/1 bytes). Draws only `len` bits worth of
+// keystream from `kdf` (the base OT's own counter-mode KDF) and unpacks 8 bits per byte, rather
+// than burning a whole SHA-256 output byte per bit.
+fn prg_bits(seed: &[u8], len: usize) -> Vec<u8> {
+    let n_bytes = (len + 7) / 8;
+    let keystream = kdf(&seed.to_vec(), 0, n_bytes);
+    (0..len).map(|i| (keystream[i / 8] >> (i % 8)) & 1).collect()
+}
+
+// H(j, q_j) expanded to exactly `len` bytes via the same counter-mode KDF the base OT uses,
+// folding the row index `j` into the nonce so identical `q_j` values across rows never collide.
+fn h_row(j: usize, q_j: &[u8], len: usize) -> Vec<u8> {
+    kdf(&q_j.to_vec(), j as u64, len)
+}
+
+fn xor(x: &[u8], y: &[u8]) -> Vec<u8> {
+    x.iter().zip(y.iter()).map(|(&a, &b)| a ^ b).collect()
+}
+
+fn columns_to_rows(cols: &[Vec<u8>], m: usize) -> Vec<Vec<u8>> {
+    (0..m).map(|j| cols.iter().map(|col| col[j]).collect()).collect()
+}
+
+fn random_seed() -> Vec<u8> {
+    (0..32).map(|_| rand::random::<u8>()).collect()
+}
+
+// Sender (extension) plays the receiver role in the KAPPA base OTs, choosing a random `s`.
+pub fn extend_sender_base_round<C: OtCurve>(
+    s: &[u8],
+    h: C,
+) -> (Vec<C::Scalar>, Vec<C>, Vec<C>, Vec<ReceiverProof<C>>) {
+    assert_eq!(s.len(), KAPPA);
+    first_round_batch(s.to_vec(), h)
+}
+
+// Receiver (extension) plays the sender role in the KAPPA base OTs, with seed pairs as inputs.
+// Also builds the T matrix and the correction vectors `u_i = G(k_{i,0}) ^ G(k_{i,1}) ^ r`.
+pub fn extend_receiver_base_round<C: OtCurve>(
+    r: &[u8],
+    h_0_vec: Vec<C>,
+    h_1_vec: Vec<C>,
+    h: C,
+    proof_vec: &Vec<ReceiverProof<C>>,
+) -> (Vec<C>, Vec<Vec<u8>>, Vec<Vec<u8>>, Vec<Vec<u8>>, ReceiverExtensionState) {
+    let m = r.len();
+    let seeds_0: Vec<Vec<u8>> = (0..KAPPA).map(|_| random_seed()).collect();
+    let seeds_1: Vec<Vec<u8>> = (0..KAPPA).map(|_| random_seed()).collect();
+    let (u_vec, v_0_vec, v_1_vec) =
+        second_round_batch(seeds_0.clone(), h_0_vec, seeds_1.clone(), h_1_vec, h, proof_vec);
+    let t_cols: Vec<Vec<u8>> = seeds_0.iter().map(|seed| prg_bits(seed, m)).collect();
+    let g_1_cols: Vec<Vec<u8>> = seeds_1.iter().map(|seed| prg_bits(seed, m)).collect();
+    let u_corr: Vec<Vec<u8>> = multizip((t_cols.iter(), g_1_cols.iter()))
+        .map(|(t_i, g_1_i)| xor(&xor(t_i, g_1_i), r))
+        .collect();
+    let state = ReceiverExtensionState { r: r.to_vec(), t_cols };
+    (u_vec, v_0_vec, v_1_vec, u_corr, state)
+}
+
+// Sender finishes the base OTs, recovers `k_{i,s_i}`, builds Q and outputs the garbled pairs.
+pub fn extend_sender<C: OtCurve>(
+    s: &[u8],
+    x_0_vec: &[Vec<u8>],
+    x_1_vec: &[Vec<u8>],
+    a_i_vec: Vec<C::Scalar>,
+    u_vec: Vec<C>,
+    v_0_vec: Vec<Vec<u8>>,
+    v_1_vec: Vec<Vec<u8>>,
+    u_corr: Vec<Vec<u8>>,
+) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let m = x_0_vec.len();
+    let seeds_s: Vec<Vec<u8>> =
+        output_computation_batch(s.to_vec(), a_i_vec, u_vec, v_0_vec, v_1_vec);
+    let q_cols: Vec<Vec<u8>> = multizip((s.iter(), seeds_s.iter(), u_corr.iter()))
+        .map(|(&s_i, seed, u_i)| {
+            let g = prg_bits(seed, m);
+            if s_i == 1 { xor(u_i, &g) } else { g }
+        })
+        .collect();
+    let q_rows = columns_to_rows(&q_cols, m);
+    let y_0_vec: Vec<Vec<u8>> = multizip((x_0_vec.iter(), q_rows.iter()))
+        .enumerate()
+        .map(|(j, (x_0, q_j))| xor(x_0, &h_row(j, q_j, x_0.len())))
+        .collect();
+    let y_1_vec: Vec<Vec<u8>> = multizip((x_1_vec.iter(), q_rows.iter()))
+        .enumerate()
+        .map(|(j, (x_1, q_j))| xor(x_1, &h_row(j, &xor(q_j, s), x_1.len())))
+        .collect();
+    (y_0_vec, y_1_vec)
+}
+
+// Receiver unmasks the message it chose using its stored T matrix row.
+pub fn extend_receiver(
+    state: ReceiverExtensionState,
+    y_0_vec: Vec<Vec<u8>>,
+    y_1_vec: Vec<Vec<u8>>,
+) -> Vec<Vec<u8>> {
+    let m = state.r.len();
+    let t_rows = columns_to_rows(&state.t_cols, m);
+    multizip((state.r.iter(), t_rows.iter(), y_0_vec.iter(), y_1_vec.iter()))
+        .enumerate()
+        .map(|(j, (&r_j, t_j, y_0, y_1))| {
+            let y = if r_j == 0 { y_0 } else { y_1 };
+            xor(y, &h_row(j, t_j, y.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curv::elliptic::curves::ed25519::GE as Ed25519Point;
+    use curv::elliptic::curves::secp256k1::GE as Secp256k1Point;
+    use rand::Rng;
+
+    fn random_bits(n: usize) -> Vec<u8> {
+        let mut rng = rand::thread_rng();
+        (0..n).map(|_| rng.gen_range(0..2)).collect()
+    }
+
+    fn iknp_extension<C: OtCurve>() {
+        let m = 16;
+        let s = random_bits(KAPPA);
+        let r = random_bits(m);
+        // 1 KiB messages exercise the fix for the previous 32-byte-only masking.
+        let x_0_vec: Vec<Vec<u8>> = (0..m).map(|_| random_seed().repeat(32)).collect();
+        let x_1_vec: Vec<Vec<u8>> = (0..m).map(|_| random_seed().repeat(32)).collect();
+
+        let h = setup::<C>();
+        let (a_i_vec, h_0_vec, h_1_vec, proof_vec) = extend_sender_base_round(&s, h);
+        let (u_vec, v_0_vec, v_1_vec, u_corr, state) =
+            extend_receiver_base_round(&r, h_0_vec, h_1_vec, h, &proof_vec);
+        let (y_0_vec, y_1_vec) = extend_sender(
+            &s, &x_0_vec, &x_1_vec, a_i_vec, u_vec, v_0_vec, v_1_vec, u_corr,
+        );
+        let out = extend_receiver(state, y_0_vec, y_1_vec);
+
+        for j in 0..m {
+            if r[j] == 0 {
+                assert_eq!(out[j], x_0_vec[j]);
+            } else {
+                assert_eq!(out[j], x_1_vec[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn iknp_extension_ed25519() {
+        iknp_extension::<Ed25519Point>();
+    }
+
+    #[test]
+    fn iknp_extension_secp256k1() {
+        iknp_extension::<Secp256k1Point>();
+    }
+}
@@ -0,0 +1,138 @@
+use super::alsz_13::{
+    first_round_batch, output_computation_random_batch, second_round_random_batch, setup, OtCurve,
+};
+use itertools::multizip;
+use rayon::prelude::*;
+
+// Online derandomization of a precomputed random OT (Beaver's OT precomputation): the receiver
+// already committed to a random bit `sigma_rand` offline and holds `m_{sigma_rand}`; online it
+// sends a single correction bit `d = sigma ^ sigma_rand` for the message it actually wants, and
+// the sender answers with two XOR-masked messages instead of running the public-key protocol again.
+
+pub struct SenderPads {
+    pub m_0: Vec<u8>,
+    pub m_1: Vec<u8>,
+}
+
+pub struct ReceiverPad {
+    pub sigma_rand: u8,
+    pub m_sigma_rand: Vec<u8>,
+}
+
+fn xor(x: &Vec<u8>, y: &Vec<u8>) -> Vec<u8> {
+    x.iter().zip(y.iter()).map(|(&a, &b)| a ^ b).collect()
+}
+
+// Offline phase: run the base OT to completion with random receiver choice bits and random
+// sender pads, producing a pool of `(SenderPads, ReceiverPad)` pairs that the online phase consumes.
+pub fn precompute<C: OtCurve>(
+    n: usize,
+    h: C,
+    pad_len: usize,
+) -> (Vec<C::Scalar>, Vec<SenderPads>, Vec<ReceiverPad>) {
+    let sigma_rand_vec: Vec<u8> = (0..n).map(|_| (rand::random::<bool>()) as u8).collect();
+    let (a_i_vec, h_0_vec, h_1_vec, proof_vec) = first_round_batch(sigma_rand_vec.clone(), h);
+    let (u_vec, m_0_vec, m_1_vec) =
+        second_round_random_batch(h_0_vec, h_1_vec, h, &proof_vec, pad_len);
+    let m_sigma_rand_vec =
+        output_computation_random_batch(a_i_vec.clone(), u_vec, pad_len);
+
+    let sender_pads: Vec<SenderPads> = multizip((m_0_vec, m_1_vec))
+        .map(|(m_0, m_1)| SenderPads { m_0, m_1 })
+        .collect();
+    let receiver_pads: Vec<ReceiverPad> = multizip((sigma_rand_vec, m_sigma_rand_vec))
+        .map(|(sigma_rand, m_sigma_rand)| ReceiverPad { sigma_rand, m_sigma_rand })
+        .collect();
+    (a_i_vec, sender_pads, receiver_pads)
+}
+
+// Online phase, sender side: given the receiver's correction bit `d`, mask the real messages
+// with the precomputed pads using only XORs.
+pub fn derandomize_sender(
+    real_x_0: &Vec<u8>,
+    real_x_1: &Vec<u8>,
+    pads: &SenderPads,
+    d: u8,
+) -> (Vec<u8>, Vec<u8>) {
+    assert!(d == 0 || d == 1);
+    let (pad_for_0, pad_for_1) = if d == 0 {
+        (&pads.m_0, &pads.m_1)
+    } else {
+        (&pads.m_1, &pads.m_0)
+    };
+    (xor(real_x_0, pad_for_0), xor(real_x_1, pad_for_1))
+}
+
+pub fn derandomize_sender_batch(
+    real_x_0_vec: &Vec<Vec<u8>>,
+    real_x_1_vec: &Vec<Vec<u8>>,
+    pads_vec: &Vec<SenderPads>,
+    d_vec: &Vec<u8>,
+) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let tmp_vec: Vec<(Vec<u8>, Vec<u8>)> = real_x_0_vec
+        .par_iter()
+        .zip(real_x_1_vec.par_iter())
+        .zip(pads_vec.par_iter())
+        .zip(d_vec.par_iter())
+        .map(|(((real_x_0, real_x_1), pads), &d)| derandomize_sender(real_x_0, real_x_1, pads, d))
+        .collect();
+    let y_0_vec = tmp_vec.iter().map(|x| x.0.clone()).collect();
+    let y_1_vec = tmp_vec.iter().map(|x| x.1.clone()).collect();
+    (y_0_vec, y_1_vec)
+}
+
+// Online phase, receiver side: the correction bit was computed so that the masked message at
+// its real choice `sigma` unmasks with the pad it already has on hand.
+pub fn correction_bit(sigma: u8, sigma_rand: u8) -> u8 {
+    sigma ^ sigma_rand
+}
+
+pub fn derandomize_receiver(sigma: u8, y_0: &Vec<u8>, y_1: &Vec<u8>, pad: &ReceiverPad) -> Vec<u8> {
+    let y_sigma = if sigma == 0 { y_0 } else { y_1 };
+    xor(y_sigma, &pad.m_sigma_rand)
+}
+
+pub fn derandomize_receiver_batch(
+    sigma_vec: &Vec<u8>,
+    y_0_vec: &Vec<Vec<u8>>,
+    y_1_vec: &Vec<Vec<u8>>,
+    pads_vec: &Vec<ReceiverPad>,
+) -> Vec<Vec<u8>> {
+    multizip((sigma_vec, y_0_vec, y_1_vec, pads_vec))
+        .map(|(&sigma, y_0, y_1, pad)| derandomize_receiver(sigma, y_0, y_1, pad))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curv::elliptic::curves::ed25519::GE as Ed25519Point;
+
+    #[test]
+    fn pool_services_chosen_message_ot_with_one_correction_bit() {
+        let n = 8;
+        let pad_len = 32;
+        let h = setup::<Ed25519Point>();
+        let (_a_i_vec, sender_pads, receiver_pads) = precompute::<Ed25519Point>(n, h, pad_len);
+
+        let x_0_vec: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; pad_len]).collect();
+        let x_1_vec: Vec<Vec<u8>> = (0..n).map(|i| vec![100 + i as u8; pad_len]).collect();
+        let sigma_vec: Vec<u8> = (0..n).map(|i| (i % 2) as u8).collect();
+
+        let d_vec: Vec<u8> = multizip((&sigma_vec, &receiver_pads))
+            .map(|(&sigma, pad)| correction_bit(sigma, pad.sigma_rand))
+            .collect();
+
+        let (y_0_vec, y_1_vec) =
+            derandomize_sender_batch(&x_0_vec, &x_1_vec, &sender_pads, &d_vec);
+        let out = derandomize_receiver_batch(&sigma_vec, &y_0_vec, &y_1_vec, &receiver_pads);
+
+        for i in 0..n {
+            if sigma_vec[i] == 0 {
+                assert_eq!(out[i], x_0_vec[i]);
+            } else {
+                assert_eq!(out[i], x_1_vec[i]);
+            }
+        }
+    }
+}